@@ -1,14 +1,21 @@
 pub use super::utils::Offset;
 pub use super::traits::{TimeZone, TimeSystem};
 use super::utils::Errors;
-use super::instant::{Era, Instant};
+use super::instant::{Duration, Era, Instant};
 use super::julian::SECONDS_PER_DAY;
+use once_cell::sync::Lazy;
 use std::fmt;
+use std::fs;
 use std::marker::Sized;
+use std::path::Path;
+use std::str;
+use std::sync::RwLock;
 
 // There is no way to define a constant map in Rust (yet), so we're combining several structures
-// to store when the leap seconds should be added. An updated list of leap seconds can be found
-// here: https://www.ietf.org/timezones/data/leap-seconds.list .
+// to store when the leap seconds should be added. These only seed `LeapSeconds::built_in()`,
+// the default table consulted by `Utc::new`, `as_instant` and `from_instant`; an updated list of
+// leap seconds can be loaded at runtime from https://www.ietf.org/timezones/data/leap-seconds.list
+// via `LeapSeconds::from_file`/`LeapSeconds::parse`, without needing a recompile.
 const JANUARY_YEARS: [i32; 17] = [
     1972,
     1973,
@@ -70,6 +77,81 @@ pub struct Utc {
     pub nanos: u32,
 }
 
+const WEEKDAY_NAMES: [&'static str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+impl Utc {
+    /// Formats this date according to `strftime`-style specifiers: `%Y` full year, `%m`/`%d`
+    /// zero-padded month/day, `%H`/`%M`/`%S` zero-padded hour/minute/second, `%f` nanoseconds
+    /// zero-padded to nine digits, `%z`/`%:z` the fixed UTC offset `+0000`/`+00:00` (written as
+    /// a literal; `Self::utc_offset()` is not consulted), `%j` the zero-padded day-of-year, and
+    /// `%A`/`%a` the full/abbreviated weekday name. `%%` yields a single `%`. Any other
+    /// `%`-escaped character is passed through literally together with its `%`, as are a
+    /// trailing lone `%` and any character not following a `%`.
+    ///
+    /// # Examples
+    /// ```
+    /// use hifitime::utc::{Utc, TimeZone};
+    ///
+    /// let santa = Utc::new(2017, 12, 25, 01, 02, 14, 0).expect("Xmas failed");
+    /// assert_eq!(santa.format("%Y-%m-%d"), "2017-12-25");
+    /// assert_eq!(santa.format("%A, %j"), "Monday, 359");
+    /// ```
+    pub fn format(&self, fmt: &str) -> String {
+        let mut out = String::with_capacity(fmt.len());
+        let mut chars = fmt.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&format!("{:04}", self.year)),
+                Some('m') => out.push_str(&format!("{:02}", self.month)),
+                Some('d') => out.push_str(&format!("{:02}", self.day)),
+                Some('H') => out.push_str(&format!("{:02}", self.hour)),
+                Some('M') => out.push_str(&format!("{:02}", self.minute)),
+                Some('S') => out.push_str(&format!("{:02}", self.second)),
+                Some('f') => out.push_str(&format!("{:09}", self.nanos)),
+                Some('z') => out.push_str("+0000"),
+                Some(':') if chars.peek() == Some(&'z') => {
+                    chars.next();
+                    out.push_str("+00:00");
+                }
+                Some('j') => out.push_str(&format!("{:03}", self.day_of_year())),
+                Some('A') => out.push_str(WEEKDAY_NAMES[self.weekday() as usize]),
+                Some('a') => out.push_str(&WEEKDAY_NAMES[self.weekday() as usize][..3]),
+                Some('%') => out.push('%'),
+                Some(other) => {
+                    out.push('%');
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+
+    /// The day of the year, where January 1st is day 1.
+    fn day_of_year(&self) -> u16 {
+        (days_from_civil(self.year, self.month as u32, self.day as u32) -
+             days_from_civil(self.year, 1, 1) + 1) as u16
+    }
+
+    /// The weekday, `0` for Monday through `6` for Sunday. 1900-01-01, day zero of this crate's
+    /// epoch, was a Monday.
+    fn weekday(&self) -> i64 {
+        days_from_civil(self.year, self.month as u32, self.day as u32).rem_euclid(7)
+    }
+}
+
 impl TimeZone for Utc
 where
     Self: Sized,
@@ -190,8 +272,11 @@ where
         if (month == 12 || month == 6) && day == USUAL_DAYS_PER_MONTH[month as usize - 1] &&
             hour == 23 && minute == 59
         {
-            if (month == 6 && JULY_YEARS.contains(&year)) ||
-                (month == 12 && JANUARY_YEARS.contains(&(year + 1)))
+            if active_leap_seconds().read().unwrap().has_leap_second_on(
+                year,
+                month,
+                day,
+            )
             {
                 max_seconds = 60;
             }
@@ -223,138 +308,1014 @@ where
 impl TimeSystem for Utc {
     /// `from_instant` converts an Instant to a Utc.
     /// Use this method to convert between different `TimeSystem` implementors.
-    fn from_instant(instant: Instant) -> Utc {
-        let (year, year_fraction) = quorem(instant.secs() as f64, 365.0 * SECONDS_PER_DAY);
-        let (mut month, month_fraction) = quorem(year_fraction, 30.4365 * SECONDS_PER_DAY);
-        month += 1; // Otherwise the month count starts at 0
-        let mut days_this_month = USUAL_DAYS_PER_MONTH[(month - 1) as usize];
-        if month == 2 && is_leap_year(year) {
-            days_this_month += 1;
-        }
-        let (mut day, day_fraction) =
-            quorem(month_fraction, SECONDS_PER_DAY * days_this_month as f64);
-        day += 1; // Otherwise the day count starts at 0
-        let (hours, hours_fraction) = quorem(day_fraction, 60.0 * 60.0);
-        let (mins, secs) = quorem(hours_fraction, 60.0);
-        match instant.era() {
-            Era::Past => {
-                Utc::new(
-                    1900 - year,
-                    month as u8,
-                    day as u8,
-                    hours as u8,
-                    mins as u8,
-                    secs as u8,
-                    instant.nanos(),
-                ).expect("date computed from instant is invalid (past)")
-            }
-            Era::Present => {
-                Utc::new(
-                    1900 + year,
-                    month as u8,
-                    day as u8,
-                    hours as u8,
-                    mins as u8,
-                    secs as u8,
-                    instant.nanos(),
-                ).expect("date computed from instant is invalid")
-            }
-        }
+    /// **NOTE:** Because two different UTC dates (the 59th and the inserted 60th second) may
+    /// share the same TAI instant (cf. `as_instant`), this always reconstructs the 59th-second
+    /// representation; there is no way to recover the ambiguous 60th second from the Instant alone.
+    /// Returns `Err(Errors::Carry)` rather than panicking if `instant` does not correspond to a
+    /// valid date, which can happen for arbitrary (e.g. adversarial or pre-1900 `Era::Past`)
+    /// instants.
+    fn from_instant(instant: Instant) -> Result<Utc, Errors> {
+        // Seconds elapsed since 1900-01-01, signed according to the instant's era. This is TAI,
+        // i.e. it already includes the cumulative leap second offset, so we must remove it before
+        // decomposing into a civil date.
+        let tai_secs = instant_to_signed_secs(instant)?;
+        // The offset can only change at the start of a day, so decomposing the raw TAI seconds
+        // gives, at worst, a date that is wrong only for the handful of seconds spanning such a
+        // boundary; use that approximation to look up a tentative offset in effect.
+        let approx_days = tai_secs.div_euclid(SECONDS_PER_DAY as i64);
+        let (approx_year, approx_month, approx_day) = civil_from_days(approx_days);
+        let tentative_offset = leap_seconds_before(approx_year, approx_month, approx_day);
+        // The date above was derived from *unadjusted* TAI seconds, which roll into the next
+        // civil day once the (not yet subtracted) offset is added back in -- e.g. the final
+        // `tentative_offset` seconds before an insertion already look like the next day in TAI.
+        // Redo the lookup using the date implied by subtracting that tentative offset, which is
+        // the actual UTC day in effect, and re-derive the offset from it.
+        let utc_days = (tai_secs - tentative_offset).div_euclid(SECONDS_PER_DAY as i64);
+        let (utc_year, utc_month, utc_day) = civil_from_days(utc_days);
+        let leap_offset = leap_seconds_before(utc_year, utc_month, utc_day);
+
+        let utc_secs = tai_secs - leap_offset;
+        let days = utc_secs.div_euclid(SECONDS_PER_DAY as i64);
+        let secs_of_day = utc_secs.rem_euclid(SECONDS_PER_DAY as i64);
+        let (year, month, day) = civil_from_days(days);
+        let hours = secs_of_day / 3600;
+        let mins = (secs_of_day % 3600) / 60;
+        let secs = secs_of_day % 60;
+        Utc::new(
+            year,
+            month,
+            day,
+            hours as u8,
+            mins as u8,
+            secs as u8,
+            instant.nanos(),
+        )
     }
 
     /// `as_instant` returns an Instant from the Utc.
     /// Also use this method to convert between different `TimeSystem` implementors
     fn as_instant(self) -> Instant {
-        let era: Era;
-        if self.year >= 1900 {
-            era = Era::Present;
+        let era = if self.year >= 1900 {
+            Era::Present
         } else {
-            era = Era::Past;
-        }
+            Era::Past
+        };
 
-        let mut seconds_wrt_1900: f64 = ((self.year - 1900).abs() as f64) * SECONDS_PER_DAY *
-            USUAL_DAYS_PER_YEAR;
-
-        // Now add the seconds for all the years prior to the current year
-        for year in 1900..self.year {
-            if is_leap_year(year) {
-                seconds_wrt_1900 += SECONDS_PER_DAY;
-            }
-        }
-        // Add the seconds for the months prior to the current month
-        for month in 0..self.month - 1 {
-            seconds_wrt_1900 += SECONDS_PER_DAY * USUAL_DAYS_PER_MONTH[(month) as usize] as f64;
-        }
-        if is_leap_year(self.year) && ((self.month == 2 && self.day == 29) || self.month > 2) {
-            seconds_wrt_1900 += SECONDS_PER_DAY;
-        }
-        seconds_wrt_1900 += (self.day - 1) as f64 * SECONDS_PER_DAY + self.hour as f64 * 3600.0 +
-            self.minute as f64 * 60.0 +
-            self.second as f64;
+        let days = days_from_civil(self.year, self.month as u32, self.day as u32);
+        let leap_offset = leap_seconds_before(self.year, self.month, self.day);
+        let mut signed_secs = days * SECONDS_PER_DAY as i64 + self.hour as i64 * 3600 +
+            self.minute as i64 * 60 + self.second as i64 + leap_offset;
         if self.second == 60 {
             // Herein lies the whole ambiguity of leap seconds. Two different UTC dates exist at the
             // same number of second afters J1900.0.
-            seconds_wrt_1900 -= 1.0;
+            signed_secs -= 1;
         }
-        Instant::new(seconds_wrt_1900 as u64, self.nanos as u32, era)
+        Instant::new(signed_secs.abs() as u64, self.nanos as u32, era)
     }
 }
 
+#[test]
+fn utc_format_time_and_fractional_seconds_test() {
+    let t = Utc::new(2021, 6, 1, 7, 8, 9, 123_000_000).expect("valid date");
+    assert_eq!(t.format("%H:%M:%S.%f"), "07:08:09.123000000");
+}
+
+#[test]
+fn utc_format_offset_specifiers_test() {
+    let t = Utc::new(2021, 6, 1, 7, 8, 9, 0).expect("valid date");
+    assert_eq!(t.format("%z"), "+0000");
+    assert_eq!(t.format("%:z"), "+00:00");
+}
+
+#[test]
+fn utc_format_literal_percent_and_unknown_specifier_test() {
+    let t = Utc::new(2021, 6, 1, 7, 8, 9, 0).expect("valid date");
+    assert_eq!(t.format("100%%"), "100%");
+    // An unrecognized specifier is passed through literally, `%` and all.
+    assert_eq!(t.format("%q"), "%q");
+    // A trailing, unescaped `%` at the end of the format string is passed through as-is.
+    assert_eq!(t.format("%"), "%");
+}
+
+#[test]
+fn utc_format_weekday_and_day_of_year_test() {
+    // 2021-01-01 was a Friday, the 1st day of the year.
+    let new_years = Utc::new(2021, 1, 1, 0, 0, 0, 0).expect("valid date");
+    assert_eq!(new_years.format("%A, %a, %j"), "Friday, Fri, 001");
+
+    // 2021-12-31 was a Friday, the 365th day of the (non-leap) year.
+    let new_years_eve = Utc::new(2021, 12, 31, 0, 0, 0, 0).expect("valid date");
+    assert_eq!(new_years_eve.format("%A, %a, %j"), "Friday, Fri, 365");
+}
+
+#[test]
+fn utc_from_instant_rejects_overflowing_era_past_instead_of_panicking_test() {
+    // An `Era::Past` instant whose `secs()` is too large to negate as an `i64` (e.g. `u64::MAX`)
+    // must not panic with "attempt to negate with overflow"; it must fall through to
+    // `Errors::Carry` like any other adversarial instant.
+    assert_eq!(
+        Utc::from_instant(Instant::new(u64::MAX, 0, Era::Past)),
+        Err(Errors::Carry)
+    );
+}
+
 impl fmt::Display for Utc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}+00:00",
-            self.year,
-            self.month,
-            self.day,
-            self.hour,
-            self.minute,
-            self.second
-        )
+        write!(f, "{}", self.format("%Y-%m-%dT%H:%M:%S%:z"))
     }
 }
 
-/// quorem returns a tuple of the quotient and the remainder a numerator and a denominator.
-fn quorem(numerator: f64, denominator: f64) -> (i32, f64) {
-    if numerator < 0.0 || denominator < 0.0 {
-        panic!("quorem only supports positive numbers");
+impl str::FromStr for Utc {
+    type Err = Errors;
+
+    /// Parses an RFC 3339 / ISO 8601 timestamp, e.g. `2017-12-25T01:02:14Z` or
+    /// `2017-12-25T01:02:14.5-05:00`, into a `Utc`. A non-`Z` offset is normalized back to UTC
+    /// before the date is constructed, and fractional seconds are mapped into `nanos`. The
+    /// `:60` leap-second form that `Utc::new` already accepts is supported as-is. Invalid dates
+    /// and malformed strings both produce `Errors::Carry`.
+    ///
+    /// # Examples
+    /// ```
+    /// use hifitime::utc::{Utc, TimeZone};
+    ///
+    /// let santa: Utc = "2017-12-25T01:02:14Z".parse().expect("Xmas failed");
+    /// assert_eq!(santa, Utc::new(2017, 12, 25, 01, 02, 14, 0).unwrap());
+    /// assert_eq!(
+    ///     "2017-12-25T01:02:14-05:00".parse::<Utc>().unwrap(),
+    ///     Utc::new(2017, 12, 25, 06, 02, 14, 0).unwrap()
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Utc, Errors> {
+        let s = s.trim();
+        // The fixed byte offsets below assume one byte per character; a multi-byte UTF-8
+        // character anywhere in the fixed-width prefix would otherwise land mid-character and
+        // panic on the subsequent slicing instead of falling through to `Errors::Carry`.
+        if !s.is_ascii() {
+            return Err(Errors::Carry);
+        }
+        if s.len() < 19 {
+            return Err(Errors::Carry);
+        }
+        let bytes = s.as_bytes();
+        if bytes[4] != b'-' || bytes[7] != b'-' || (bytes[10] != b'T' && bytes[10] != b't') ||
+            bytes[13] != b':' || bytes[16] != b':'
+        {
+            return Err(Errors::Carry);
+        }
+        let year: i32 = s[0..4].parse().map_err(|_| Errors::Carry)?;
+        let month: u8 = s[5..7].parse().map_err(|_| Errors::Carry)?;
+        let day: u8 = s[8..10].parse().map_err(|_| Errors::Carry)?;
+        let hour: u8 = s[11..13].parse().map_err(|_| Errors::Carry)?;
+        let minute: u8 = s[14..16].parse().map_err(|_| Errors::Carry)?;
+        let second: u8 = s[17..19].parse().map_err(|_| Errors::Carry)?;
+
+        let mut rest = &s[19..];
+        let mut nanos: u32 = 0;
+        if rest.starts_with('.') {
+            let frac_len = rest[1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len() - 1);
+            let frac = &rest[1..1 + frac_len];
+            let padded: String = frac.chars().chain(std::iter::repeat('0')).take(9).collect();
+            nanos = padded.parse().map_err(|_| Errors::Carry)?;
+            rest = &rest[1 + frac_len..];
+        }
+
+        let offset_secs: i64 = if rest.eq_ignore_ascii_case("z") {
+            0
+        } else if rest.len() == 6 && (rest.starts_with('+') || rest.starts_with('-')) &&
+                   rest.as_bytes()[3] == b':'
+        {
+            let sign: i64 = if rest.starts_with('-') { -1 } else { 1 };
+            let offset_hour: i64 = rest[1..3].parse().map_err(|_| Errors::Carry)?;
+            let offset_minute: i64 = rest[4..6].parse().map_err(|_| Errors::Carry)?;
+            if offset_hour > 23 || offset_minute > 59 {
+                return Err(Errors::Carry);
+            }
+            sign * (offset_hour * 3600 + offset_minute * 60)
+        } else {
+            return Err(Errors::Carry);
+        };
+
+        let local = Utc::new(year, month, day, hour, minute, second, nanos)?;
+        if offset_secs == 0 {
+            return Ok(local);
+        }
+        let offset_duration = Duration::new(offset_secs.abs() as u64, 0);
+        let utc_instant = if offset_secs > 0 {
+            local.as_instant() - offset_duration
+        } else {
+            local.as_instant() + offset_duration
+        };
+        Utc::from_instant(utc_instant)
+    }
+}
+
+#[test]
+fn utc_from_str_rejects_non_ascii_instead_of_panicking_test() {
+    // A multi-byte UTF-8 character landing inside the fixed-width byte offsets used above must
+    // not panic with a "byte index is not a char boundary" error.
+    assert_eq!(
+        "2017-12-25T01:02:1é".parse::<Utc>(),
+        Err(Errors::Carry)
+    );
+}
+
+#[test]
+fn utc_from_str_rejects_out_of_range_offset_test() {
+    // The offset's hour/minute fields must be bounds-checked like every other field `Utc::new`
+    // validates; `+99:99` must not be silently folded into a ~100-hour shift.
+    assert_eq!(
+        "2017-12-25T01:02:14+99:99".parse::<Utc>(),
+        Err(Errors::Carry)
+    );
+    assert_eq!(
+        "2017-12-25T01:02:14+24:00".parse::<Utc>(),
+        Err(Errors::Carry)
+    );
+    assert_eq!(
+        "2017-12-25T01:02:14+00:60".parse::<Utc>(),
+        Err(Errors::Carry)
+    );
+}
+
+/// A single UTC-offset transition parsed out of a TZif file, sorted into `Zoned::transitions`
+/// by `at`.
+#[derive(Clone, Debug, PartialEq)]
+struct ZoneTransition {
+    /// The instant at which this offset starts applying, in seconds since the Unix epoch
+    /// (1970-01-01), matching the resolution of the TZif transition time table.
+    at: i64,
+    /// Local time minus UTC, in seconds.
+    utc_offset_secs: i32,
+    is_dst: bool,
+}
+
+/// A named IANA time zone (e.g. `America/New_York`) backed by compiled zoneinfo (TZif) data,
+/// capable of expressing an offset that changes across the year such as daylight saving time.
+/// **NOTE:** `TimeZone::utc_offset()` is a zone-data-agnostic associated function with no
+/// instant argument, so it cannot express an offset that depends on both the zone *and* the
+/// instant being converted; `Zoned` therefore does not implement `TimeZone` and instead exposes
+/// `offset_at`/`new` directly. Supporting this through `TimeZone` would require that trait to
+/// carry `&self` and an `Instant`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zoned {
+    pub name: String,
+    transitions: Vec<ZoneTransition>,
+}
+
+/// A wall-clock date/time that cannot be resolved unambiguously in a given `Zoned` zone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ZonedError {
+    /// This time was skipped entirely by a forward (e.g. spring-forward) transition.
+    Nonexistent,
+    /// This time occurred twice because of a backward (e.g. fall-back) transition.
+    Ambiguous,
+}
+
+impl Zoned {
+    /// Parses the contents of a TZif (binary zoneinfo, e.g. a file under
+    /// `/usr/share/zoneinfo/`) blob. Only the TZif version 1, 32-bit transition-time header and
+    /// body are read; the higher-precision 64-bit block some systems append after it is
+    /// ignored, so only transitions within the signed 32-bit Unix range (1901-12-13 to
+    /// 2038-01-19) are seen, and the last of them stays in effect for every later instant.
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<Zoned, Errors> {
+        if data.len() < 44 || &data[0..4] != b"TZif" {
+            return Err(Errors::Carry);
+        }
+        let read_u32 = |off: usize| -> Result<u32, Errors> {
+            if off + 4 > data.len() {
+                return Err(Errors::Carry);
+            }
+            Ok(
+                ((data[off] as u32) << 24) | ((data[off + 1] as u32) << 16) |
+                    ((data[off + 2] as u32) << 8) | (data[off + 3] as u32),
+            )
+        };
+
+        let isutcnt = read_u32(20)? as usize;
+        let isstdcnt = read_u32(24)? as usize;
+        let leapcnt = read_u32(28)? as usize;
+        let timecnt = read_u32(32)? as usize;
+        let typecnt = read_u32(36)? as usize;
+        let charcnt = read_u32(40)? as usize;
+
+        // `timecnt`/`typecnt` come straight from the (untrusted) header and are about to size
+        // `Vec::with_capacity` allocations; check the body is actually large enough to back that
+        // many records before trusting them, rather than letting a header claiming e.g.
+        // `timecnt = 0xFFFFFFFF` drive a multi-gigabyte allocation attempt.
+        let body_len = timecnt
+            .checked_mul(5)
+            .and_then(|n| n.checked_add(typecnt.checked_mul(6)?))
+            .ok_or(Errors::Carry)?;
+        if 44 + body_len > data.len() {
+            return Err(Errors::Carry);
+        }
+
+        let mut off = 44;
+        let mut times = Vec::with_capacity(timecnt);
+        for _ in 0..timecnt {
+            times.push(read_u32(off)? as i32 as i64);
+            off += 4;
+        }
+        let mut type_indices = Vec::with_capacity(timecnt);
+        for _ in 0..timecnt {
+            type_indices.push(*data.get(off).ok_or(Errors::Carry)?);
+            off += 1;
+        }
+        struct TType {
+            utc_offset_secs: i32,
+            is_dst: bool,
+        }
+        let mut types = Vec::with_capacity(typecnt);
+        for _ in 0..typecnt {
+            let utc_offset_secs = read_u32(off)? as i32;
+            let is_dst = *data.get(off + 4).ok_or(Errors::Carry)? != 0;
+            types.push(TType {
+                utc_offset_secs,
+                is_dst,
+            });
+            off += 6; // 4-byte signed offset, 1-byte isdst flag, 1-byte abbreviation index
+        }
+        // The abbreviation strings, leap second records, and std/wall and UT/local indicators
+        // that follow are not needed to resolve offsets, so they're skipped rather than parsed.
+        let _ = (charcnt, leapcnt, isstdcnt, isutcnt);
+
+        let mut transitions = Vec::with_capacity(timecnt);
+        for (at, &type_index) in times.into_iter().zip(type_indices.iter()) {
+            let t = types.get(type_index as usize).ok_or(Errors::Carry)?;
+            transitions.push(ZoneTransition {
+                at,
+                utc_offset_secs: t.utc_offset_secs,
+                is_dst: t.is_dst,
+            });
+        }
+        transitions.sort_by_key(|t| t.at);
+        Ok(Zoned {
+            name: name.to_string(),
+            transitions,
+        })
     }
-    if denominator == 0.0 {
-        panic!("cannot divide by zero");
+
+    /// The offset (local minus UTC, in seconds) in effect at the given TAI instant; falls back
+    /// to zero (i.e. UTC) before the zone's first recorded transition. Returns
+    /// `Err(Errors::Carry)` if `instant` cannot be converted to Unix seconds (cf.
+    /// `instant_to_unix_secs`).
+    pub fn offset_at(&self, instant: Instant) -> Result<i32, Errors> {
+        Ok(self.offset_at_unix(instant_to_unix_secs(instant)?))
     }
+
+    fn offset_at_unix(&self, unix_secs: i64) -> i32 {
+        self.transitions
+            .iter()
+            .rev()
+            .find(|t| t.at <= unix_secs)
+            .map(|t| t.utc_offset_secs)
+            .unwrap_or(0)
+    }
+
+    /// Resolves a civil wall-clock date/time in this zone to the `Instant` it represents.
+    /// Returns `ZonedError::Nonexistent` if this time was skipped by a spring-forward gap, and
+    /// `ZonedError::Ambiguous` if it was repeated by a fall-back overlap.
+    pub fn new(
+        &self,
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanos: u32,
+    ) -> Result<Instant, ZonedError> {
+        let utc = Utc::new(year, month, day, hour, minute, second, nanos)
+            .map_err(|_| ZonedError::Nonexistent)?;
+        let naive = instant_to_unix_secs(utc.as_instant()).map_err(|_| ZonedError::Nonexistent)?;
+
+        // Each segment is a half-open UTC range `[start, end)` during which a single offset
+        // applies, exactly mirroring `offset_at_unix`: zero before the first transition, and the
+        // last transition's offset forever after. A wall-clock moment resolves against a segment
+        // only if subtracting *that segment's own offset* from it lands back inside that same
+        // segment -- probing a literal +/-2h window around the unadjusted wall-clock number
+        // instead assumed the zone's standard offset was close to zero, which silently broke
+        // every zone whose standard offset is not (e.g. America/New_York at -5h).
+        let mut segments: Vec<(i64, i64, i32)> = Vec::with_capacity(self.transitions.len() + 1);
+        let mut start = i64::MIN;
+        let mut offset = 0;
+        for t in &self.transitions {
+            segments.push((start, t.at, offset));
+            start = t.at;
+            offset = t.utc_offset_secs;
+        }
+        segments.push((start, i64::MAX, offset));
+
+        let mut candidates: Vec<i64> = Vec::new();
+        for (seg_start, seg_end, seg_offset) in segments {
+            let utc_secs = naive - seg_offset as i64;
+            if utc_secs >= seg_start && utc_secs < seg_end && !candidates.contains(&utc_secs) {
+                candidates.push(utc_secs);
+            }
+        }
+        match candidates.len() {
+            0 => Err(ZonedError::Nonexistent),
+            1 => Ok(unix_secs_to_instant(candidates[0], nanos)),
+            _ => Err(ZonedError::Ambiguous),
+        }
+    }
+}
+
+/// Builds a minimal TZif version 1 blob with a fixed STD offset, a spring-forward transition
+/// into a DST offset, and a fall-back transition back to STD, for use by the `Zoned` tests.
+#[cfg(test)]
+fn synthetic_tzif(std_offset: i32, dst_offset: i32, spring_forward: i64, fall_back: i64) -> Vec<u8> {
+    let epoch = (days_from_civil(2000, 1, 1) - 25567) * SECONDS_PER_DAY as i64;
+    let mut data = Vec::new();
+    data.extend_from_slice(b"TZif");
+    data.push(0); // version 1
+    data.extend_from_slice(&[0u8; 15]); // reserved
+    data.extend_from_slice(&0u32.to_be_bytes()); // isutcnt
+    data.extend_from_slice(&0u32.to_be_bytes()); // isstdcnt
+    data.extend_from_slice(&0u32.to_be_bytes()); // leapcnt
+    data.extend_from_slice(&3u32.to_be_bytes()); // timecnt
+    data.extend_from_slice(&2u32.to_be_bytes()); // typecnt
+    data.extend_from_slice(&0u32.to_be_bytes()); // charcnt
+    for &t in &[epoch, spring_forward, fall_back] {
+        data.extend_from_slice(&(t as i32 as u32).to_be_bytes());
+    }
+    for &type_index in &[0u8, 1, 0] {
+        data.push(type_index);
+    }
+    for &(offset, is_dst) in &[(std_offset, 0u8), (dst_offset, 1u8)] {
+        data.extend_from_slice(&(offset as u32).to_be_bytes());
+        data.push(is_dst);
+        data.push(0); // abbreviation index, unused by `from_tzif`
+    }
+    data
+}
+
+// A London-like zone: GMT (UTC+0) in winter, BST (UTC+1) in summer. The clocks go forward at
+// 01:00 UTC on the spring transition and back at 01:00 UTC on the fall transition.
+#[cfg(test)]
+fn synthetic_bst_tzif() -> (Vec<u8>, i64, i64) {
+    let spring_forward = (days_from_civil(2021, 3, 28) - 25567) * SECONDS_PER_DAY as i64 + 3600;
+    let fall_back = (days_from_civil(2021, 10, 31) - 25567) * SECONDS_PER_DAY as i64 + 3600;
     (
-        (numerator / denominator).floor() as i32,
-        (numerator % denominator),
+        synthetic_tzif(0, 3600, spring_forward, fall_back),
+        spring_forward,
+        fall_back,
     )
 }
 
 #[test]
-fn quorem_nominal_test() {
-    assert_eq!(quorem(24.0, 6.0), (4, 0.0));
-    assert_eq!(quorem(25.0, 6.0), (4, 1.0));
-    assert_eq!(quorem(6.0, 6.0), (1, 0.0));
-    assert_eq!(quorem(5.0, 6.0), (0, 5.0));
-    assert_eq!(quorem(3540.0, 3600.0), (0, 3540.0));
-    assert_eq!(quorem(3540.0, 60.0), (59, 0.0));
+fn zoned_from_tzif_rejects_header_counts_exceeding_buffer_test() {
+    let mut data = Vec::new();
+    data.extend_from_slice(b"TZif");
+    data.push(0); // version 1
+    data.extend_from_slice(&[0u8; 15]); // reserved
+    data.extend_from_slice(&0u32.to_be_bytes()); // isutcnt
+    data.extend_from_slice(&0u32.to_be_bytes()); // isstdcnt
+    data.extend_from_slice(&0u32.to_be_bytes()); // leapcnt
+    data.extend_from_slice(&0xFFFF_FFFFu32.to_be_bytes()); // timecnt: claims far more than exists
+    data.extend_from_slice(&0xFFFF_FFFFu32.to_be_bytes()); // typecnt: ditto
+    data.extend_from_slice(&0u32.to_be_bytes()); // charcnt
+    // No actual body follows, so this must be rejected before any allocation is sized off the
+    // header's claimed counts.
+    assert_eq!(Zoned::from_tzif("Test/Zone", &data), Err(Errors::Carry));
 }
 
 #[test]
-#[should_panic]
-fn quorem_negative_num_test() {
-    assert_eq!(quorem(-24.0, 6.0), (4, 0.0));
+fn zoned_from_tzif_offset_at_test() {
+    let (data, spring_forward, fall_back) = synthetic_bst_tzif();
+    let zone = Zoned::from_tzif("Test/Zone", &data).expect("valid synthetic TZif blob");
+
+    assert_eq!(zone.offset_at(unix_secs_to_instant(spring_forward - 3600, 0)), Ok(0));
+    assert_eq!(zone.offset_at(unix_secs_to_instant(spring_forward + 3600, 0)), Ok(3600));
+    assert_eq!(zone.offset_at(unix_secs_to_instant(fall_back + 3600, 0)), Ok(0));
 }
 
 #[test]
-#[should_panic]
-fn quorem_negative_den_test() {
-    assert_eq!(quorem(24.0, -6.0), (4, 0.0));
+fn zoned_offset_at_does_not_leak_tai_leap_offset_test() {
+    // Regression test: `offset_at` must resolve a `Utc::as_instant()` (which bakes in the
+    // cumulative TAI-UTC leap second offset) against genuinely leap-free Unix/UTC seconds, not
+    // against raw epoch-shifted TAI seconds. 2021-03-28T00:59:40Z is 20 seconds before the
+    // synthetic BST spring-forward at 01:00:00Z, so it must still read as GMT (offset 0); were
+    // the leap offset (37s as of 2021) left in, it would land 17 seconds *after* the transition.
+    let (data, _, _) = synthetic_bst_tzif();
+    let zone = Zoned::from_tzif("Test/Zone", &data).expect("valid synthetic TZif blob");
+
+    let just_before = Utc::new(2021, 3, 28, 0, 59, 40, 0).expect("valid date").as_instant();
+    assert_eq!(zone.offset_at(just_before), Ok(0));
+}
+
+#[test]
+fn zoned_new_nonexistent_on_spring_forward_gap_test() {
+    let (data, _, _) = synthetic_bst_tzif();
+    let zone = Zoned::from_tzif("Test/Zone", &data).expect("valid synthetic TZif blob");
+
+    // 01:30 local on 2021-03-28 was skipped entirely: clocks jumped from 00:59:59 GMT straight
+    // to 02:00:00 BST.
+    assert_eq!(
+        zone.new(2021, 3, 28, 1, 30, 0, 0),
+        Err(ZonedError::Nonexistent)
+    );
+}
+
+#[test]
+fn zoned_new_ambiguous_on_fall_back_overlap_test() {
+    let (data, _, _) = synthetic_bst_tzif();
+    let zone = Zoned::from_tzif("Test/Zone", &data).expect("valid synthetic TZif blob");
+
+    // 01:30 local on 2021-10-31 occurred twice: once in BST before the fall-back, once in GMT
+    // after it.
+    assert_eq!(
+        zone.new(2021, 10, 31, 1, 30, 0, 0),
+        Err(ZonedError::Ambiguous)
+    );
+
+    // An unambiguous summer date still resolves normally, using the BST offset.
+    let noon = zone.new(2021, 6, 1, 12, 0, 0, 0).expect("unambiguous date");
+    assert_eq!(zone.offset_at(noon), Ok(3600));
+}
+
+// A New York-like zone: EST (UTC-5) in winter, EDT (UTC-4) in summer. Unlike `synthetic_bst_tzif`,
+// the standard offset here is far from zero, which is what the +/-2h literal probe window used to
+// get wrong for the majority of real IANA zones.
+#[cfg(test)]
+fn synthetic_nyc_tzif() -> (Vec<u8>, i64, i64) {
+    // Spring-forward: clocks jump from 01:59:59 EST (-5) to 03:00:00 EDT (-4) at 2021-03-14 02:00
+    // local, i.e. 07:00 UTC.
+    let spring_forward = (days_from_civil(2021, 3, 14) - 25567) * SECONDS_PER_DAY as i64 +
+        7 * 3600;
+    // Fall-back: clocks jump from 01:59:59 EDT (-4) back to 01:00:00 EST (-5) at 2021-11-07 02:00
+    // local, i.e. 06:00 UTC.
+    let fall_back = (days_from_civil(2021, 11, 7) - 25567) * SECONDS_PER_DAY as i64 + 6 * 3600;
+    (
+        synthetic_tzif(-5 * 3600, -4 * 3600, spring_forward, fall_back),
+        spring_forward,
+        fall_back,
+    )
+}
+
+#[test]
+fn zoned_from_tzif_offset_at_nonzero_standard_offset_test() {
+    let (data, spring_forward, fall_back) = synthetic_nyc_tzif();
+    let zone = Zoned::from_tzif("Test/NYC", &data).expect("valid synthetic TZif blob");
+
+    assert_eq!(zone.offset_at(unix_secs_to_instant(spring_forward - 3600, 0)), Ok(-5 * 3600));
+    assert_eq!(zone.offset_at(unix_secs_to_instant(spring_forward + 3600, 0)), Ok(-4 * 3600));
+    assert_eq!(zone.offset_at(unix_secs_to_instant(fall_back + 3600, 0)), Ok(-5 * 3600));
+}
+
+#[test]
+fn zoned_new_nonexistent_on_spring_forward_gap_nonzero_standard_offset_test() {
+    let (data, _, _) = synthetic_nyc_tzif();
+    let zone = Zoned::from_tzif("Test/NYC", &data).expect("valid synthetic TZif blob");
+
+    // 02:30 local on 2021-03-14 was skipped entirely: clocks jumped from 01:59:59 EST straight to
+    // 03:00:00 EDT. A +/-2h literal probe window around the unadjusted wall-clock number would
+    // have missed the true UTC instant here, since it is 7 hours away from the naive guess.
+    assert_eq!(
+        zone.new(2021, 3, 14, 2, 30, 0, 0),
+        Err(ZonedError::Nonexistent)
+    );
+}
+
+#[test]
+fn zoned_new_ambiguous_on_fall_back_overlap_nonzero_standard_offset_test() {
+    let (data, _, _) = synthetic_nyc_tzif();
+    let zone = Zoned::from_tzif("Test/NYC", &data).expect("valid synthetic TZif blob");
+
+    // 01:30 local on 2021-11-07 occurred twice: once in EDT before the fall-back, once in EST
+    // after it.
+    assert_eq!(
+        zone.new(2021, 11, 7, 1, 30, 0, 0),
+        Err(ZonedError::Ambiguous)
+    );
+
+    // Unambiguous dates on both sides of the standard offset still resolve correctly.
+    let summer_noon = zone.new(2021, 6, 1, 12, 0, 0, 0).expect("unambiguous summer date");
+    assert_eq!(zone.offset_at(summer_noon), Ok(-4 * 3600));
+
+    let winter_noon = zone.new(2021, 1, 1, 12, 0, 0, 0).expect("unambiguous winter date");
+    assert_eq!(zone.offset_at(winter_noon), Ok(-5 * 3600));
+}
+
+/// Converts a TAI `Instant` (seconds since 1900-01-01, including the cumulative TAI-UTC leap
+/// second offset) to Unix time (leap-free seconds since 1970-01-01), the scale TZif transition
+/// times are expressed in. This routes through `Utc::from_instant`, which already strips the
+/// leap offset for the instant's resolved UTC date, rather than just shifting the epoch, so the
+/// result stays in sync with `Utc::as_instant`/`from_instant` across every leap second insertion.
+fn instant_to_unix_secs(instant: Instant) -> Result<i64, Errors> {
+    let utc = Utc::from_instant(instant)?;
+    let unix_days = days_from_civil(utc.year, utc.month as u32, utc.day as u32) - 25567;
+    Ok(unix_days * SECONDS_PER_DAY as i64 + utc.hour as i64 * 3600 + utc.minute as i64 * 60 +
+        utc.second as i64)
+}
+
+/// The inverse of `instant_to_unix_secs`: decomposes leap-free Unix seconds into a civil UTC
+/// date/time and re-encodes it via `Utc::as_instant`, which adds back the cumulative leap second
+/// offset in effect on that date.
+fn unix_secs_to_instant(unix_secs: i64, nanos: u32) -> Instant {
+    let unix_days = unix_secs.div_euclid(SECONDS_PER_DAY as i64);
+    let secs_of_day = unix_secs.rem_euclid(SECONDS_PER_DAY as i64);
+    let (year, month, day) = civil_from_days(unix_days + 25567);
+    Utc {
+        year,
+        month,
+        day,
+        hour: (secs_of_day / 3600) as u8,
+        minute: ((secs_of_day % 3600) / 60) as u8,
+        second: (secs_of_day % 60) as u8,
+        nanos,
+    }.as_instant()
+}
+
+/// A runtime-loadable table of leap second insertions, in the format published by the IETF at
+/// https://www.ietf.org/timezones/data/leap-seconds.list . Each entry is an NTP timestamp
+/// (seconds since 1900-01-01) paired with the cumulative TAI-UTC offset that takes effect from
+/// that timestamp onward, so unlike `JANUARY_YEARS`/`JULY_YEARS` a entry's offset need not be
+/// exactly one second more than the previous one: this is what lets future positive *or*
+/// negative leap seconds be represented from the file alone, without a new hifitime release.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeapSeconds {
+    /// Sorted ascending by NTP timestamp.
+    entries: Vec<(i64, i64)>,
+    /// The `#@` expiration timestamp of the table that produced this, if any (NTP seconds).
+    pub expires: Option<i64>,
+}
+
+impl LeapSeconds {
+    /// The table built from this crate's compiled-in `JANUARY_YEARS`/`JULY_YEARS` constants.
+    /// **WARNING:** Before 1972-01-01 (where this table starts) the real TAI-UTC offset crept up
+    /// gradually via fractional "rubber seconds" that this crate does not model, per the
+    /// historical-oddities warning on `Utc`. Because the first entry seeds the real TAI-UTC = 10
+    /// already in effect by then, rather than counting up from the pre-1972 default of zero,
+    /// `Instant` arithmetic spanning 1971-12-31 -> 1972-01-01 sees the offset jump by 10 seconds
+    /// instead of the usual 1, so 23:59:59 and the following 00:00:00 lie 11 seconds apart; do
+    /// not rely on `Instant`/`Duration` arithmetic across that date.
+    pub fn built_in() -> LeapSeconds {
+        let mut times: Vec<i64> = JANUARY_YEARS
+            .iter()
+            .map(|&y| days_from_civil(y, 1, 1) * SECONDS_PER_DAY as i64)
+            .chain(
+                JULY_YEARS
+                    .iter()
+                    .map(|&y| days_from_civil(y, 7, 1) * SECONDS_PER_DAY as i64),
+            )
+            .collect();
+        times.sort();
+        let entries = times
+            .into_iter()
+            .enumerate()
+            // The first entry (1972-01-01) is TAI-UTC = 10, the offset already in effect when
+            // leap seconds began; each subsequent entry adds one more.
+            .map(|(i, t)| (t, i as i64 + 10))
+            .collect();
+        LeapSeconds {
+            entries,
+            expires: None,
+        }
+    }
+
+    /// Parses a `leap-seconds.list` file's contents. Blank lines and `#` comment lines are
+    /// skipped, except for a `#@` line, which gives the table's expiration time. Each data line
+    /// is `<NTP seconds since 1900-01-01> <cumulative TAI-UTC offset>`.
+    pub fn parse(data: &[u8]) -> Result<LeapSeconds, Errors> {
+        let text = str::from_utf8(data).map_err(|_| Errors::Carry)?;
+        let mut entries = Vec::new();
+        let mut expires = None;
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with("#@") {
+                expires = line[2..].trim().split_whitespace().next().and_then(
+                    |s| s.parse::<i64>().ok(),
+                );
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let ntp_secs: i64 = fields
+                .next()
+                .ok_or(Errors::Carry)?
+                .parse()
+                .map_err(|_| Errors::Carry)?;
+            let offset: i64 = fields
+                .next()
+                .ok_or(Errors::Carry)?
+                .parse()
+                .map_err(|_| Errors::Carry)?;
+            entries.push((ntp_secs, offset));
+        }
+        entries.sort_by_key(|&(t, _)| t);
+        Ok(LeapSeconds { entries, expires })
+    }
+
+    /// Loads and parses a `leap-seconds.list` file from disk.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<LeapSeconds, Errors> {
+        let data = fs::read(path).map_err(|_| Errors::Carry)?;
+        LeapSeconds::parse(&data)
+    }
+
+    /// Installs this table as the one consulted by `Utc::new`, `as_instant` and `from_instant`.
+    pub fn install(self) {
+        *active_leap_seconds().write().unwrap() = self;
+    }
+
+    /// The cumulative TAI-UTC offset in effect at the start of the given civil date.
+    fn offset_before(&self, year: i32, month: u8, day: u8) -> i64 {
+        let day_start = days_from_civil(year, month as u32, day as u32) * SECONDS_PER_DAY as i64;
+        self.entries
+            .iter()
+            .rev()
+            .find(|&&(t, _)| t <= day_start)
+            .map(|&(_, o)| o)
+            .unwrap_or(0)
+    }
+
+    /// True if a leap second is inserted at the end of the given UTC day, i.e. if `23:59:60` is
+    /// a valid time on that day.
+    fn has_leap_second_on(&self, year: i32, month: u8, day: u8) -> bool {
+        let next_day_start =
+            (days_from_civil(year, month as u32, day as u32) + 1) * SECONDS_PER_DAY as i64;
+        let mut prev_offset = 0;
+        for &(t, offset) in &self.entries {
+            if t == next_day_start {
+                // Only an increase of the offset inserts a second; a decrease removes one.
+                return offset > prev_offset;
+            }
+            prev_offset = offset;
+        }
+        false
+    }
+}
+
+#[test]
+fn leap_seconds_parse_test() {
+    let jan_1972 = days_from_civil(1972, 1, 1) * SECONDS_PER_DAY as i64;
+    let jul_1972 = days_from_civil(1972, 7, 1) * SECONDS_PER_DAY as i64;
+    let jan_1973 = days_from_civil(1973, 1, 1) * SECONDS_PER_DAY as i64;
+    let data = format!(
+        "#\tleap-seconds.list test snippet\n\
+         #@\t3833986496\n\
+         {}\t10\t# 1 Jan 1972\n\
+         \n\
+         {}\t11\t# 1 Jul 1972\n\
+         {}\t9\t# synthetic decrementing entry, e.g. a removed leap second\n",
+        jan_1972, jul_1972, jan_1973
+    );
+    let table = LeapSeconds::parse(data.as_bytes()).expect("valid leap-seconds.list snippet");
+    assert_eq!(table.expires, Some(3833986496));
+    assert_eq!(table.offset_before(1971, 12, 31), 0);
+    assert_eq!(table.offset_before(1972, 1, 1), 10);
+    assert_eq!(table.offset_before(1972, 6, 30), 10);
+    assert_eq!(table.offset_before(1972, 7, 1), 11);
+    assert_eq!(table.offset_before(1972, 12, 31), 11);
+    assert_eq!(table.offset_before(1973, 1, 1), 9);
+    assert!(table.has_leap_second_on(1971, 12, 31));
+    assert!(table.has_leap_second_on(1972, 6, 30));
+    assert!(!table.has_leap_second_on(1972, 1, 1));
+}
+
+#[test]
+fn leap_seconds_built_in_matches_real_world_offset_test() {
+    // 2017-12-25 is after the last published leap second insertion (2017-01-01), at which point
+    // the real-world TAI-UTC offset reached 37 (not the 28 that counting "1 per entry" from zero
+    // would give), so this also pins `Utc::as_instant`/`from_instant` to genuine TAI.
+    let table = LeapSeconds::built_in();
+    assert_eq!(table.offset_before(2017, 12, 25), 37);
+
+    let santa = Utc::new(2017, 12, 25, 01, 02, 14, 0).expect("Xmas failed");
+    let instant = santa.as_instant();
+    assert_eq!(Utc::from_instant(instant), Ok(santa));
+}
+
+#[test]
+fn leap_seconds_built_in_has_a_known_pre_1972_discontinuity_test() {
+    // The table's first entry seeds the real TAI-UTC = 10 already in effect by 1972-01-01
+    // (cf. the `WARNING` on `built_in`), rather than counting up from the pre-1972 default of
+    // zero, so `Instant` arithmetic spanning this one date sees the offset jump by 10 seconds
+    // instead of the usual 1, putting 23:59:59 and 00:00:00 11 seconds apart. This is a known, deliberate limitation, not a bug to fix here --
+    // this test pins the documented behavior so a future change doesn't silently alter it.
+    let eve = Utc::new(1971, 12, 31, 23, 59, 59, 0).expect("valid date").as_instant();
+    let day = Utc::new(1972, 1, 1, 0, 0, 0, 0).expect("valid date").as_instant();
+    assert_eq!(instant_seconds_f64(day) - instant_seconds_f64(eve), 11.0);
+}
+
+#[test]
+fn leap_seconds_parse_rejects_malformed_line_test() {
+    assert_eq!(
+        LeapSeconds::parse(b"not a valid line at all"),
+        Err(Errors::Carry)
+    );
+}
+
+static ACTIVE_LEAP_SECONDS: Lazy<RwLock<LeapSeconds>> =
+    Lazy::new(|| RwLock::new(LeapSeconds::built_in()));
+
+/// Returns the `LeapSeconds` table currently consulted by `Utc`, lazily initialized to
+/// `LeapSeconds::built_in()` on first use.
+fn active_leap_seconds() -> &'static RwLock<LeapSeconds> {
+    &ACTIVE_LEAP_SECONDS
+}
+
+/// `leap_seconds_before` returns the cumulative TAI-UTC offset in effect on the given civil
+/// date, as reported by the currently active `LeapSeconds` table.
+fn leap_seconds_before(year: i32, month: u8, day: u8) -> i64 {
+    active_leap_seconds().read().unwrap().offset_before(year, month, day)
+}
+
+/// Converts an `Instant`'s `(secs, era)` pair into a single signed count of seconds elapsed
+/// since 1900-01-01 (negative for `Era::Past`). Returns `Err(Errors::Carry)` rather than
+/// overflowing/panicking when `instant.secs()` is too large to negate as an `i64`, which is
+/// reachable from arbitrary (e.g. adversarial or pre-1900 `Era::Past`) instants.
+fn instant_to_signed_secs(instant: Instant) -> Result<i64, Errors> {
+    if instant.secs() > i64::MAX as u64 {
+        return Err(Errors::Carry);
+    }
+    let secs = instant.secs() as i64;
+    Ok(match instant.era() {
+        Era::Past => -secs,
+        Era::Present => secs,
+    })
+}
+
+/// Converts a value of one `TimeSystem` into another, always routing through `Instant` (TAI) as
+/// the pivot scale: `t.as_instant()` followed by `To::from_instant(..)`. This means supporting N
+/// timescales only requires 2N offset functions (one pair per `TimeSystem` impl), rather than
+/// N^2 direct conversions between every pair. Because `Utc::as_instant`/`Utc::from_instant`
+/// already consult the active `LeapSeconds` table, any scale converted through `Utc` round-trips
+/// correctly across leap seconds too.
+pub fn convert<From: TimeSystem, To: TimeSystem>(t: From) -> Result<To, Errors> {
+    To::from_instant(t.as_instant())
+}
+
+/// TAI, International Atomic Time: a trivial `TimeSystem` wrapper around `Instant`, since
+/// `Instant` already counts seconds without any leap-second or relativistic correction.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Tai(pub Instant);
+
+impl TimeSystem for Tai {
+    fn from_instant(instant: Instant) -> Result<Tai, Errors> {
+        Ok(Tai(instant))
+    }
+
+    fn as_instant(self) -> Instant {
+        self.0
+    }
+}
+
+/// GPS time, as broadcast by GPS satellites: a fixed, leap-second-free offset from TAI,
+/// `GPS = TAI - 19s`: GPS time lags TAI by the 19s TAI-UTC offset in effect at the GPS epoch
+/// (1980-01-06), when GPS time was aligned with UTC.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Gps(pub Instant);
+
+impl TimeSystem for Gps {
+    fn from_instant(instant: Instant) -> Result<Gps, Errors> {
+        Ok(Gps(offset_instant(instant, -19.0)))
+    }
+
+    fn as_instant(self) -> Instant {
+        offset_instant(self.0, 19.0)
+    }
+}
+
+/// Terrestrial Time, used for apparent geocentric ephemerides: a fixed offset from TAI,
+/// `TT = TAI + 32.184s`.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Tt(pub Instant);
+
+const TT_MINUS_TAI_SECONDS: f64 = 32.184;
+
+impl TimeSystem for Tt {
+    fn from_instant(instant: Instant) -> Result<Tt, Errors> {
+        Ok(Tt(offset_instant(instant, TT_MINUS_TAI_SECONDS)))
+    }
+
+    fn as_instant(self) -> Instant {
+        offset_instant(self.0, -TT_MINUS_TAI_SECONDS)
+    }
+}
+
+/// Barycentric Dynamical Time, used for solar-system-barycentric ephemerides. `TDB` and `TT`
+/// differ only by a small periodic term driven by the Earth's position in its orbit, per the
+/// standard approximation `TDB - TT = 0.001658*sin(M) + 0.000014*sin(2M)` seconds, where `M` is
+/// Earth's mean anomaly as a linear function of time since the J2000.0 epoch.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Tdb(pub Instant);
+
+impl TimeSystem for Tdb {
+    fn from_instant(instant: Instant) -> Result<Tdb, Errors> {
+        let tt = offset_instant(instant, TT_MINUS_TAI_SECONDS);
+        Ok(Tdb(offset_instant(tt, tdb_minus_tt_seconds(tt))))
+    }
+
+    fn as_instant(self) -> Instant {
+        let tt = offset_instant(self.0, -tdb_minus_tt_seconds(self.0));
+        offset_instant(tt, -TT_MINUS_TAI_SECONDS)
+    }
+}
+
+#[test]
+fn gps_tai_offset_test() {
+    let tai = Instant::new(1_000_000_000, 0, Era::Present);
+    let gps = Gps::from_instant(tai).unwrap();
+    assert_eq!(instant_seconds_f64(gps.0) - instant_seconds_f64(tai), -19.0);
+    assert_eq!(gps.as_instant(), tai);
+}
+
+#[test]
+fn tt_tai_offset_test() {
+    let tai = Instant::new(1_000_000_000, 0, Era::Present);
+    let tt = Tt::from_instant(tai).unwrap();
+    assert!((instant_seconds_f64(tt.0) - instant_seconds_f64(tai) - 32.184).abs() < 1e-6);
+    assert_eq!(tt.as_instant(), tai);
 }
 
 #[test]
-#[should_panic]
-fn quorem_negative_numden_test() {
-    // A valid argument could be made that this test should work, but there is no situation in
-    // this library where two negative numbers should be considered a valid input.
-    assert_eq!(quorem(-24.0, -6.0), (4, 0.0));
+fn tdb_round_trips_through_tt_test() {
+    let tai = Instant::new(1_000_000_000, 0, Era::Present);
+    let tdb = Tdb::from_instant(tai).unwrap();
+    // TDB-TAI is TT-TAI (32.184s) plus a periodic term no larger than ~0.002s.
+    assert!((instant_seconds_f64(tdb.0) - instant_seconds_f64(tai) - 32.184).abs() < 0.01);
+    assert_eq!(tdb.as_instant(), tai);
 }
+
+#[test]
+fn convert_routes_through_tai_test() {
+    let tai = Tai(Instant::new(500_000_000, 0, Era::Present));
+    let gps: Gps = convert(tai).unwrap();
+    let back: Tai = convert(gps).unwrap();
+    assert_eq!(back, tai);
+}
+
+/// The periodic `TDB - TT` correction, in seconds, for a TT instant.
+fn tdb_minus_tt_seconds(tt: Instant) -> f64 {
+    let tt_secs = instant_seconds_f64(tt);
+    let j2000_secs = days_from_civil(2000, 1, 1) as f64 * SECONDS_PER_DAY + 43_200.0;
+    let days_since_j2000 = (tt_secs - j2000_secs) / SECONDS_PER_DAY;
+    let mean_anomaly = 6.239_996 + 0.017_201_969_65 * days_since_j2000; // radians
+    0.001_658 * mean_anomaly.sin() + 0.000_014 * (2.0 * mean_anomaly).sin()
+}
+
+/// The signed number of seconds (with fractional nanoseconds) that `instant` is offset from the
+/// 1900-01-01 epoch.
+fn instant_seconds_f64(instant: Instant) -> f64 {
+    let secs = instant.secs() as f64 + instant.nanos() as f64 * 1e-9;
+    match instant.era() {
+        Era::Past => -secs,
+        Era::Present => secs,
+    }
+}
+
+/// Shifts `instant` by `delta_secs` (which may be negative or fractional), used by the fixed
+/// and periodic timescale offsets above.
+fn offset_instant(instant: Instant, delta_secs: f64) -> Instant {
+    let magnitude = delta_secs.abs();
+    let duration = Duration::new(magnitude.trunc() as u64, (magnitude.fract() * 1e9).round() as u32);
+    if delta_secs >= 0.0 {
+        instant + duration
+    } else {
+        instant - duration
+    }
+}
+
+/// `civil_from_days` converts a day count (relative to the 1900-01-01 epoch, which may be
+/// negative for dates before it) into a proleptic Gregorian `(year, month, day)` triple.
+/// This is Howard Hinnant's exact, allocation-free `civil_from_days` algorithm
+/// (http://howardhinnant.github.io/date_algorithms.html#civil_from_days), shifted so that
+/// `days == 0` lands on 1900-01-01 instead of 1970-01-01.
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    // 719468 shifts a days-since-1970-01-01 count to days-since-0000-03-01; since our `days` is
+    // relative to 1900-01-01, which is 25567 days before 1970-01-01, subtracting that many from
+    // the shift gives days-since-0000-03-01 directly.
+    let z = days + (719468 - 25567);
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8; // [1, 31]
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8; // [1, 12]
+    let year = (y + if month <= 2 { 1 } else { 0 }) as i32;
+    (year, month, day)
+}
+
+/// `days_from_civil` is the inverse of `civil_from_days`: it converts a proleptic Gregorian
+/// `(year, month, day)` triple into a day count relative to the 1900-01-01 epoch.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as u64; // [0, 399]
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy as u64; // [0, 146096]
+    // era*146097 + doe - 719468 gives days since 1970-01-01; since 1900-01-01 is 25567 days
+    // before that, adding 25567 back shifts the result to days-since-1900-01-01.
+    era * 146097 + doe as i64 - (719468 - 25567)
+}
+